@@ -124,13 +124,14 @@
 //!     }
 //! }
 //! ```
-use actix::{Actor, ActorFuture};
+use actix::{Actor, ActorFuture, ActorStream};
 use pin_project_lite::pin_project;
 use std::{
+    mem::ManuallyDrop,
     pin::Pin,
     task::{Context, Poll},
 };
-use tracing::Span;
+use tracing::{Dispatch, Span};
 
 /// Extension trait allowing actor futures to be instrumented with
 /// a `tracing` `Span`.
@@ -141,7 +142,30 @@ pub trait ActorInstrument: Sized {
     /// When the wrapped actor future is polled, the attached `Span`
     /// will be entered for the duration of the poll.
     fn actor_instrument(self, span: Span) -> ActorInstrumented<Self> {
-        ActorInstrumented { inner: self, span }
+        ActorInstrumented {
+            inner: ManuallyDrop::new(self),
+            span,
+        }
+    }
+
+    /// Instruments this type with the provided `Span`, falling back to the
+    /// current span if the provided one is disabled.
+    ///
+    /// If the provided `Span` is disabled (e.g. filtered out by the
+    /// subscriber), the [current span] is attached instead. This mirrors
+    /// [`Span::or_current`] and is useful when deferred actor work should
+    /// still be threaded through the live current span rather than producing
+    /// orphaned events.
+    ///
+    /// [current span]: tracing::Span::current
+    /// [`Span::or_current`]: tracing::Span::or_current
+    fn or_current_actor_instrument(self, span: Span) -> ActorInstrumented<Self> {
+        let span = if span.is_disabled() {
+            Span::current()
+        } else {
+            span
+        };
+        self.actor_instrument(span)
     }
 
     #[inline]
@@ -157,10 +181,25 @@ pin_project! {
     #[derive(Debug, Clone)]
     pub struct ActorInstrumented<T>
     {
+        // `ManuallyDrop` so that the attached span can be entered for the
+        // duration of `inner`'s destructor (see the `PinnedDrop` impl below),
+        // matching how `tracing`'s `Instrumented` attributes drop-time events.
         #[pin]
-        inner: T,
+        inner: ManuallyDrop<T>,
         span: Span,
     }
+
+    impl<T> PinnedDrop for ActorInstrumented<T> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            let _enter = this.span.enter();
+            // Safety: `inner` is not used again after this, and the enclosing
+            // value is being dropped.
+            unsafe {
+                ManuallyDrop::drop(this.inner.get_unchecked_mut());
+            }
+        }
+    }
 }
 
 impl<T: ActorFuture<U>, U: Actor> ActorFuture<U> for ActorInstrumented<T> {
@@ -174,7 +213,9 @@ impl<T: ActorFuture<U>, U: Actor> ActorFuture<U> for ActorInstrumented<T> {
     ) -> Poll<Self::Output> {
         let this = self.project();
         let _enter = this.span.enter();
-        this.inner.poll(srv, ctx, task)
+        // Safety: dereferencing the `ManuallyDrop` yields the pinned inner.
+        let inner = unsafe { this.inner.map_unchecked_mut(|m| &mut **m) };
+        inner.poll(srv, ctx, task)
     }
 }
 
@@ -199,6 +240,207 @@ impl<T> ActorInstrumented<T> {
         &mut self.inner
     }
 
+    /// Get a pinned reference to the wrapped type.
+    pub fn inner_pin_ref(self: Pin<&Self>) -> Pin<&T> {
+        // Safety: the `ManuallyDrop` projection is structurally pinned.
+        unsafe { self.project_ref().inner.map_unchecked(|m| &**m) }
+    }
+
+    /// Get a pinned mutable reference to the wrapped type.
+    pub fn inner_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        // Safety: the `ManuallyDrop` projection is structurally pinned.
+        unsafe { self.project().inner.map_unchecked_mut(|m| &mut **m) }
+    }
+
+    /// Consumes the `ActorInstrumented`, returning the wrapped type.
+    ///
+    /// Note that this drops the span.
+    pub fn into_inner(self) -> T {
+        // Move the inner value out without running our `PinnedDrop`, which
+        // would otherwise drop `inner` a second time.
+        let mut this = ManuallyDrop::new(self);
+        // Safety: `this` is never dropped or used again, so moving out of its
+        // fields is sound; `inner` is taken exactly once here.
+        unsafe {
+            let inner = ManuallyDrop::take(&mut this.inner);
+            std::ptr::drop_in_place(&mut this.span);
+            inner
+        }
+    }
+}
+
+/// Extension trait allowing actor futures to be routed to a particular
+/// `tracing` [`Dispatch`] (subscriber) while they run.
+pub trait WithActorSubscriber: Sized {
+    /// Attaches the provided subscriber to this type, returning a
+    /// `WithActorDispatch` wrapper.
+    ///
+    /// The attached subscriber will be set as the [default] for the duration
+    /// of every poll, so that any spans and events produced while the actor
+    /// future runs are routed to it regardless of the thread-local default on
+    /// the arbiter thread.
+    ///
+    /// [default]: tracing::dispatcher#setting-the-default-subscriber
+    fn with_subscriber(self, subscriber: impl Into<Dispatch>) -> WithActorDispatch<Self> {
+        WithActorDispatch {
+            inner: self,
+            dispatch: subscriber.into(),
+        }
+    }
+
+    /// Attaches the current [default] subscriber to this type, returning a
+    /// `WithActorDispatch` wrapper.
+    ///
+    /// [default]: tracing::dispatcher#setting-the-default-subscriber
+    #[inline]
+    fn with_current_subscriber(self) -> WithActorDispatch<Self> {
+        WithActorDispatch {
+            inner: self,
+            dispatch: tracing::dispatcher::get_default(|default| default.clone()),
+        }
+    }
+
+    /// Instruments this type with the provided `Span` *and* attaches the
+    /// provided subscriber, so that the actor future is both entered into the
+    /// span and routed to the subscriber while it runs.
+    fn actor_instrument_with_subscriber(
+        self,
+        span: Span,
+        subscriber: impl Into<Dispatch>,
+    ) -> WithActorDispatch<ActorInstrumented<Self>> {
+        self.actor_instrument(span).with_subscriber(subscriber)
+    }
+}
+
+impl<T: Sized> WithActorSubscriber for T {}
+
+pin_project! {
+    /// An actor future that has been attached to a `tracing` [`Dispatch`].
+    #[derive(Debug, Clone)]
+    pub struct WithActorDispatch<T> {
+        #[pin]
+        inner: T,
+        dispatch: Dispatch,
+    }
+}
+
+impl<T: ActorFuture<U>, U: Actor> ActorFuture<U> for WithActorDispatch<T> {
+    type Output = <T as ActorFuture<U>>::Output;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        srv: &mut U,
+        ctx: &mut U::Context,
+        task: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        let this = self.project();
+        let dispatch = this.dispatch;
+        let inner = this.inner;
+        tracing::dispatcher::with_default(dispatch, || inner.poll(srv, ctx, task))
+    }
+}
+
+impl<T> WithActorDispatch<T> {
+    /// Borrows the `Dispatch` that this type is attached to.
+    pub fn dispatch(&self) -> &Dispatch {
+        &self.dispatch
+    }
+
+    /// Borrows the wrapped type.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutably borrows the wrapped type.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Get a pinned reference to the wrapped type.
+    pub fn inner_pin_ref(self: Pin<&Self>) -> Pin<&T> {
+        self.project_ref().inner
+    }
+
+    /// Get a pinned mutable reference to the wrapped type.
+    pub fn inner_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    /// Consumes the `WithActorDispatch`, returning the wrapped type.
+    ///
+    /// Note that this drops the dispatch.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Extension trait allowing actor streams to be instrumented with
+/// a `tracing` `Span`.
+pub trait ActorStreamInstrument: Sized {
+    /// Instruments this type with the provided `Span`, returning an
+    /// `ActorStreamInstrumented` wrapper.
+    ///
+    /// When the wrapped actor stream is polled, the attached `Span`
+    /// will be entered for the duration of each `poll_next`.
+    fn actor_stream_instrument(self, span: Span) -> ActorStreamInstrumented<Self> {
+        ActorStreamInstrumented { inner: self, span }
+    }
+
+    #[inline]
+    fn in_current_actor_stream_span(self) -> ActorStreamInstrumented<Self> {
+        self.actor_stream_instrument(Span::current())
+    }
+}
+
+impl<T: Sized> ActorStreamInstrument for T {}
+
+pin_project! {
+    /// An actor stream that has been instrumented with a `tracing` span.
+    #[derive(Debug, Clone)]
+    pub struct ActorStreamInstrumented<T>
+    {
+        #[pin]
+        inner: T,
+        span: Span,
+    }
+}
+
+impl<T: ActorStream<U>, U: Actor> ActorStream<U> for ActorStreamInstrumented<T> {
+    type Item = <T as ActorStream<U>>::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        srv: &mut U,
+        ctx: &mut U::Context,
+        task: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let _enter = this.span.enter();
+        this.inner.poll_next(srv, ctx, task)
+    }
+}
+
+impl<T> ActorStreamInstrumented<T> {
+    /// Borrows the `Span` that this type is instrumented by.
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Mutably borrows the `Span` that this type is instrumented by.
+    pub fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+
+    /// Borrows the wrapped type.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutably borrows the wrapped type.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
     /// Get a pinned reference to the wrapped type.
     pub fn inner_pin_ref(self: Pin<&Self>) -> Pin<&T> {
         self.project_ref().inner
@@ -209,10 +451,240 @@ impl<T> ActorInstrumented<T> {
         self.project().inner
     }
 
-    /// Consumes the `Instrumented`, returning the wrapped type.
+    /// Consumes the `ActorStreamInstrumented`, returning the wrapped type.
     ///
     /// Note that this drops the span.
     pub fn into_inner(self) -> T {
         self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Record};
+    use tracing::{event, span, Event, Id, Level, Metadata, Subscriber};
+
+    /// A minimal `Subscriber` that records, for every event it receives, the
+    /// name of the span that was current when the event fired. Used to assert
+    /// that events are attributed to the expected span.
+    #[derive(Clone, Default)]
+    struct CollectingSubscriber {
+        inner: Arc<Inner>,
+    }
+
+    #[derive(Default)]
+    struct Inner {
+        next_id: Mutex<u64>,
+        names: Mutex<HashMap<u64, &'static str>>,
+        stack: Mutex<Vec<u64>>,
+        events: Mutex<Vec<Option<&'static str>>>,
+    }
+
+    impl CollectingSubscriber {
+        /// The name of the current span recorded for each event seen so far.
+        fn events(&self) -> Vec<Option<&'static str>> {
+            self.inner.events.lock().unwrap().clone()
+        }
+    }
+
+    impl Subscriber for CollectingSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut next = self.inner.next_id.lock().unwrap();
+            *next += 1;
+            self.inner
+                .names
+                .lock()
+                .unwrap()
+                .insert(*next, span.metadata().name());
+            Id::from_u64(*next)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {
+            let current = self
+                .inner
+                .stack
+                .lock()
+                .unwrap()
+                .last()
+                .and_then(|id| self.inner.names.lock().unwrap().get(id).copied());
+            self.inner.events.lock().unwrap().push(current);
+        }
+
+        fn enter(&self, span: &Id) {
+            self.inner.stack.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _span: &Id) {
+            self.inner.stack.lock().unwrap().pop();
+        }
+    }
+
+    /// An actor future whose `Drop` emits an event, used to check that the
+    /// attached span is entered for the duration of destruction.
+    struct DropEvent;
+
+    impl Drop for DropEvent {
+        fn drop(&mut self) {
+            event!(Level::INFO, "dropped");
+        }
+    }
+
+    impl ActorFuture<TestActor> for DropEvent {
+        type Output = ();
+
+        fn poll(
+            self: Pin<&mut Self>,
+            _srv: &mut TestActor,
+            _ctx: &mut actix::Context<TestActor>,
+            _task: &mut Context<'_>,
+        ) -> Poll<Self::Output> {
+            Poll::Ready(())
+        }
+    }
+
+    #[test]
+    fn drop_event_carries_span_context() {
+        let subscriber = CollectingSubscriber::default();
+        let handle = subscriber.clone();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = span!(Level::INFO, "drop span");
+            let instrumented = DropEvent.actor_instrument(span);
+            drop(instrumented);
+        });
+        // The event emitted from `DropEvent::drop` must have fired while the
+        // attached span was entered.
+        assert_eq!(handle.events(), vec![Some("drop span")]);
+    }
+
+    /// An actor stream that emits an event from within each `poll_next` before
+    /// yielding the item, used to check that per-item processing is attributed
+    /// to the attached span.
+    struct ItemStream {
+        remaining: u32,
+    }
+
+    impl ActorStream<TestActor> for ItemStream {
+        type Item = u32;
+
+        fn poll_next(
+            mut self: Pin<&mut Self>,
+            _srv: &mut TestActor,
+            _ctx: &mut actix::Context<TestActor>,
+            _task: &mut Context<'_>,
+        ) -> Poll<Option<Self::Item>> {
+            if self.remaining == 0 {
+                return Poll::Ready(None);
+            }
+            self.remaining -= 1;
+            event!(Level::INFO, "stream item");
+            Poll::Ready(Some(self.remaining))
+        }
+    }
+
+    struct TestActor;
+
+    impl Actor for TestActor {
+        type Context = actix::Context<Self>;
+
+        fn started(&mut self, ctx: &mut Self::Context) {
+            use actix::fut::{ActorFutureExt, ActorStreamExt};
+            use actix::AsyncContext;
+
+            let span = span!(Level::INFO, "stream span");
+            let fut = ItemStream { remaining: 3 }
+                .actor_stream_instrument(span)
+                .finish()
+                .map(|_, _, _| actix::System::current().stop());
+            ctx.spawn(fut);
+        }
+    }
+
+    #[test]
+    fn stream_items_land_in_span() {
+        let subscriber = CollectingSubscriber::default();
+        let handle = subscriber.clone();
+        tracing::subscriber::with_default(subscriber, || {
+            let system = actix::System::new();
+            system.block_on(async {
+                TestActor.start();
+            });
+            system.run().unwrap();
+        });
+        // One event per streamed item, each fired while the handler span was
+        // entered during `poll_next`.
+        assert_eq!(
+            handle.events(),
+            vec![Some("stream span"), Some("stream span"), Some("stream span")]
+        );
+    }
+
+    /// An actor future that emits an event from within its `poll`, used to
+    /// check which subscriber that event is routed to.
+    struct EventFuture;
+
+    impl ActorFuture<DispatchActor> for EventFuture {
+        type Output = ();
+
+        fn poll(
+            self: Pin<&mut Self>,
+            _srv: &mut DispatchActor,
+            _ctx: &mut actix::Context<DispatchActor>,
+            _task: &mut Context<'_>,
+        ) -> Poll<Self::Output> {
+            event!(Level::INFO, "dispatched");
+            Poll::Ready(())
+        }
+    }
+
+    struct DispatchActor {
+        dispatch: Dispatch,
+    }
+
+    impl Actor for DispatchActor {
+        type Context = actix::Context<Self>;
+
+        fn started(&mut self, ctx: &mut Self::Context) {
+            use actix::fut::ActorFutureExt;
+            use actix::AsyncContext;
+
+            let fut = EventFuture
+                .with_subscriber(self.dispatch.clone())
+                .map(|_, _, _| actix::System::current().stop());
+            ctx.spawn(fut);
+        }
+    }
+
+    #[test]
+    fn dispatch_routes_events_to_attached_subscriber() {
+        let default_sub = CollectingSubscriber::default();
+        let default_handle = default_sub.clone();
+        let target_sub = CollectingSubscriber::default();
+        let target_handle = target_sub.clone();
+        let dispatch = Dispatch::new(target_sub);
+
+        tracing::subscriber::with_default(default_sub, || {
+            let system = actix::System::new();
+            system.block_on(async move {
+                DispatchActor { dispatch }.start();
+            });
+            system.run().unwrap();
+        });
+
+        // The event fired inside `poll` is routed to the attached dispatch
+        // (no span is entered, so its current span is `None`)...
+        assert_eq!(target_handle.events(), vec![None]);
+        // ...and never reaches the arbiter's thread-local default subscriber.
+        assert!(default_handle.events().is_empty());
+    }
+}